@@ -0,0 +1,49 @@
+//! Pure args-hashing logic, kept in its own module (rather than inline in `main.rs`) so it can
+//! be unit tested without pulling in that binary's `#![no_main]`/`sp1_zkvm::entrypoint!` zkVM
+//! harness.
+
+use ckb_vm::Bytes;
+use sha2::{Digest, Sha256};
+
+/// Digest over the argument vector, so the public values bind the proof to a specific set of
+/// CKB script args without committing their (potentially large) raw bytes.
+pub fn args_digest(args: &[Bytes]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for arg in args {
+        hasher.update((arg.len() as u64).to_le_bytes());
+        hasher.update(arg);
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_args_is_deterministic() {
+        assert_eq!(args_digest(&[]), args_digest(&[]));
+    }
+
+    #[test]
+    fn digest_changes_with_arg_contents() {
+        let a = args_digest(&[Bytes::from_static(b"hello")]);
+        let b = args_digest(&[Bytes::from_static(b"world")]);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn digest_is_not_confused_by_concatenation_across_args() {
+        // Without length-prefixing, [b"ab", b"c"] and [b"a", b"bc"] would hash identically.
+        let split_early = args_digest(&[Bytes::from_static(b"ab"), Bytes::from_static(b"c")]);
+        let split_late = args_digest(&[Bytes::from_static(b"a"), Bytes::from_static(b"bc")]);
+        assert_ne!(split_early, split_late);
+    }
+
+    #[test]
+    fn digest_is_sensitive_to_arg_order() {
+        let forward = args_digest(&[Bytes::from_static(b"a"), Bytes::from_static(b"b")]);
+        let reversed = args_digest(&[Bytes::from_static(b"b"), Bytes::from_static(b"a")]);
+        assert_ne!(forward, reversed);
+    }
+}
@@ -0,0 +1,36 @@
+//! Aggregates N CKB-VM interpreter proofs into a single recursive proof. Verifies each job's
+//! compressed proof via `sp1_zkvm::lib::verify`, then commits a Merkle root over the ordered
+//! (program hash, args digest, tx hash, exit code, cycle count) tuples so a single on-chain
+//! verification binds the whole batch to the specific scripts *and transactions* it covers.
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+#[path = "../merkle.rs"]
+mod merkle;
+use merkle::{leaf_hash, merkle_root};
+
+fn main() {
+    let job_count: u64 = sp1_zkvm::io::read();
+    let vkeys: Vec<[u32; 8]> = sp1_zkvm::io::read();
+    let public_values: Vec<Vec<u8>> = sp1_zkvm::io::read();
+    assert_eq!(vkeys.len(), job_count as usize, "vkeys/job_count mismatch");
+    assert_eq!(
+        public_values.len(),
+        job_count as usize,
+        "public_values/job_count mismatch"
+    );
+
+    let mut leaves = Vec::with_capacity(job_count as usize);
+    for (vkey, public_values) in vkeys.iter().zip(public_values.iter()) {
+        let pv_digest: [u8; 32] = Sha256::digest(public_values).into();
+        sp1_zkvm::lib::verify::verify_sp1_proof(vkey, &pv_digest);
+
+        leaves.push(leaf_hash(public_values));
+    }
+
+    let root = merkle_root(leaves);
+    sp1_zkvm::io::commit(&root);
+    sp1_zkvm::io::commit(&job_count);
+}
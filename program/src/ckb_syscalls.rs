@@ -0,0 +1,414 @@
+//! CKB syscall ABI implementation, serving a transaction context supplied by the host.
+//!
+//! Syscall numbers, return codes and the partial-load (offset/length) calling convention
+//! follow RFC 0009 ("VM Syscalls"): a7 carries the syscall number, a0/a1 the destination
+//! address and in/out length, and the remaining registers are syscall-specific (cell index,
+//! source, field). See
+//! https://github.com/nervosnetwork/rfcs/blob/master/rfcs/0009-vm-syscalls/0009-vm-syscalls.md
+//!
+//! ## Limitations
+//! Only the "whole value" loaders are implemented: `ckb_load_tx_hash`, `ckb_load_script`,
+//! `ckb_load_cell`, `ckb_load_cell_data` and `ckb_load_witness`, plus `ckb_debug` as a no-op.
+//! The field-selecting syscalls (`ckb_load_cell_by_field`, `ckb_load_input_by_field`,
+//! `ckb_load_header_by_field`) and `ckb_vm_version`/`ckb_current_cycles` are not handled:
+//! [`TxContext`] stores cells as opaque serialized bytes rather than a parsed Molecule
+//! `CellOutput`, so there is no single field to extract without first adding that schema.
+//! Real-world lock/type scripts commonly call `ckb_load_cell_by_field` to cheaply read a lock
+//! or type hash, so such scripts will hit the unhandled-ecall path below and abort; only
+//! scripts restricted to the implemented subset, like the bundled secp256k1 fixture, are
+//! guaranteed to run end-to-end today.
+//!
+//! `ckb_load_witness` only honors `Source::Input`: [`TxContext`] models witnesses as a single
+//! flat array rather than tracking which half belongs to inputs vs. outputs, so there is no
+//! real data to serve for `Source::Output`/`Source::CellDep`/group sources — those return
+//! `CKB_INDEX_OUT_OF_BOUND` rather than silently handing back the wrong witness.
+
+use ckb_vm::registers::{A0, A1, A2, A3, A4, A7};
+use ckb_vm::{Error, Memory, Register, SupportMachine, Syscalls};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+const SYS_LOAD_TX_HASH: u64 = 2061;
+const SYS_LOAD_SCRIPT: u64 = 2052;
+const SYS_LOAD_CELL: u64 = 2071;
+const SYS_LOAD_CELL_DATA: u64 = 2092;
+const SYS_LOAD_WITNESS: u64 = 2074;
+const SYS_DEBUG: u64 = 2177;
+
+/// Reserved syscall number for the accelerated host ECDSA recover, far above the numbers CKB
+/// assigns to its own syscalls (all in the low thousands, e.g. 2177 for `ckb_debug`) so it can
+/// never collide with a real CKB syscall. A CKB script calls this instead of doing secp256k1
+/// field/group math in interpreted RISC-V; the handler runs the same recovery on the
+/// already-hashed message through the outer, SP1-patched `k256` crate, so the proof hits SP1's
+/// accelerated secp256k1 precompiles for the dominant crypto step.
+const SYS_CKB_VM_ECDSA_RECOVER: u64 = 0xff00_0001;
+
+const CKB_SUCCESS: u64 = 0;
+const CKB_INDEX_OUT_OF_BOUND: u64 = 1;
+const CKB_ITEM_MISSING: u64 = 2;
+const CKB_INVALID_DATA: u64 = 4;
+
+/// Which side of the transaction a cell/witness index refers to, matching the CKB `Source`
+/// enum used by real scripts (`ckb_std::ckb_constants::Source`). `GroupInput`/`GroupOutput`
+/// collapse onto `Input`/`Output`: this guest always proves one script in isolation, so the
+/// host is expected to have already curated `TxContext` down to that script's own cells —
+/// there's no larger batch here to narrow a "group" out of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Source {
+    Input,
+    Output,
+    CellDep,
+}
+
+/// Real CKB `Source` register values (`ckb_std::ckb_constants::Source`), including the group
+/// variants collapsed onto their plain counterparts above.
+const SOURCE_INPUT: u64 = 1;
+const SOURCE_OUTPUT: u64 = 2;
+const SOURCE_CELL_DEP: u64 = 3;
+const SOURCE_GROUP_INPUT: u64 = 0x0100000000000001;
+const SOURCE_GROUP_OUTPUT: u64 = 0x0100000000000002;
+
+/// The slice of a transaction a CKB script needs to run: its own cells, witnesses and script,
+/// written to `SP1Stdin` by the host and deserialized here before execution starts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TxContext {
+    pub tx_hash: [u8; 32],
+    pub input_cells: Vec<Vec<u8>>,
+    pub output_cells: Vec<Vec<u8>>,
+    pub input_cell_data: Vec<Vec<u8>>,
+    pub output_cell_data: Vec<Vec<u8>>,
+    #[serde(default)]
+    pub cell_deps: Vec<Vec<u8>>,
+    #[serde(default)]
+    pub cell_dep_data: Vec<Vec<u8>>,
+    pub witnesses: Vec<Vec<u8>>,
+    pub script: Vec<u8>,
+}
+
+impl TxContext {
+    fn cell(&self, index: usize, source: Source) -> Option<&[u8]> {
+        match source {
+            Source::Input => self.input_cells.get(index).map(Vec::as_slice),
+            Source::Output => self.output_cells.get(index).map(Vec::as_slice),
+            Source::CellDep => self.cell_deps.get(index).map(Vec::as_slice),
+        }
+    }
+
+    fn cell_data(&self, index: usize, source: Source) -> Option<&[u8]> {
+        match source {
+            Source::Input => self.input_cell_data.get(index).map(Vec::as_slice),
+            Source::Output => self.output_cell_data.get(index).map(Vec::as_slice),
+            Source::CellDep => self.cell_dep_data.get(index).map(Vec::as_slice),
+        }
+    }
+}
+
+fn source_from_register(value: u64) -> Option<Source> {
+    match value {
+        SOURCE_INPUT | SOURCE_GROUP_INPUT => Some(Source::Input),
+        SOURCE_OUTPUT | SOURCE_GROUP_OUTPUT => Some(Source::Output),
+        SOURCE_CELL_DEP => Some(Source::CellDep),
+        _ => None,
+    }
+}
+
+/// Implements the subset of the CKB syscall ABI needed to run scripts that inspect their own
+/// transaction: tx hash, cells, cell data, witnesses and the running script itself, plus
+/// `ckb_debug` as a no-op. Any other ecall falls through to [`Syscalls::ecall`] returning
+/// `Ok(false)`, matching how real CKB-VM lets later syscall handlers in the chain take over —
+/// see the module-level doc for which real CKB syscalls that affects.
+pub struct CkbSyscalls {
+    pub tx: TxContext,
+}
+
+impl CkbSyscalls {
+    /// Copies `data[offset..]` into guest memory at `addr`, bounded by the caller's requested
+    /// length at `addr_len`, and writes back the full remaining length so the caller can tell
+    /// whether the read was truncated. This is the CKB "partial load" convention used by every
+    /// `ckb_load_*` syscall.
+    fn store<Mac: SupportMachine>(
+        machine: &mut Mac,
+        data: &[u8],
+        addr: u64,
+        addr_len: u64,
+        offset: u64,
+    ) -> Result<(), Error> {
+        let offset = offset as usize;
+        let full_len = data.len().saturating_sub(offset) as u64;
+        let requested_len = machine.memory_mut().load64(&Mac::REG::from_u64(addr_len))?;
+        let copy_len = full_len.min(requested_len.to_u64()) as usize;
+        if copy_len > 0 {
+            machine
+                .memory_mut()
+                .store_bytes(addr, &data[offset..offset + copy_len])?;
+        }
+        machine.memory_mut().store64(
+            &Mac::REG::from_u64(addr_len),
+            &Mac::REG::from_u64(full_len),
+        )?;
+        Ok(())
+    }
+
+    /// Serves a `ckb_load_*` syscall whose payload is either missing (`ITEM_MISSING`) or a
+    /// concrete byte slice, and sets the return register accordingly.
+    fn serve<Mac: SupportMachine>(
+        machine: &mut Mac,
+        data: Option<&[u8]>,
+        addr: u64,
+        addr_len: u64,
+        offset: u64,
+    ) -> Result<(), Error> {
+        let code = match data {
+            Some(data) => {
+                Self::store(machine, data, addr, addr_len, offset)?;
+                CKB_SUCCESS
+            }
+            None => CKB_ITEM_MISSING,
+        };
+        machine.set_register(A0, Mac::REG::from_u64(code));
+        Ok(())
+    }
+
+    /// Recovers the secp256k1 public key for `message_hash`/`signature`/`recovery_id` using the
+    /// outer `k256` crate, and writes the 64-byte uncompressed (no `0x04` prefix) pubkey to
+    /// `out_addr` on success.
+    fn ecdsa_recover<Mac: SupportMachine>(
+        machine: &mut Mac,
+        message_hash: &[u8; 32],
+        signature: &[u8; 64],
+        recovery_id: u8,
+        out_addr: u64,
+    ) -> Result<(), Error> {
+        let code = (|| -> Option<[u8; 64]> {
+            let signature = Signature::from_slice(signature).ok()?;
+            let recovery_id = RecoveryId::from_byte(recovery_id)?;
+            let pubkey =
+                VerifyingKey::recover_from_prehash(message_hash, &signature, recovery_id).ok()?;
+            let point = pubkey.to_encoded_point(false);
+            point.as_bytes()[1..].try_into().ok()
+        })();
+
+        let code = match code {
+            Some(pubkey) => {
+                machine.memory_mut().store_bytes(out_addr, &pubkey)?;
+                CKB_SUCCESS
+            }
+            None => CKB_INVALID_DATA,
+        };
+        machine.set_register(A0, Mac::REG::from_u64(code));
+        Ok(())
+    }
+}
+
+impl<Mac: SupportMachine> Syscalls<Mac> for CkbSyscalls {
+    fn initialize(&mut self, _machine: &mut Mac) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn ecall(&mut self, machine: &mut Mac) -> Result<bool, Error> {
+        let code = machine.registers()[A7].to_u64();
+
+        match code {
+            SYS_LOAD_TX_HASH => {
+                let addr = machine.registers()[A0].to_u64();
+                let addr_len = machine.registers()[A1].to_u64();
+                let offset = machine.registers()[A2].to_u64();
+                Self::serve(machine, Some(&self.tx.tx_hash), addr, addr_len, offset)?;
+            }
+            SYS_LOAD_SCRIPT => {
+                let addr = machine.registers()[A0].to_u64();
+                let addr_len = machine.registers()[A1].to_u64();
+                let offset = machine.registers()[A2].to_u64();
+                Self::serve(machine, Some(&self.tx.script), addr, addr_len, offset)?;
+            }
+            SYS_LOAD_CELL | SYS_LOAD_CELL_DATA => {
+                let addr = machine.registers()[A0].to_u64();
+                let addr_len = machine.registers()[A1].to_u64();
+                let offset = machine.registers()[A2].to_u64();
+                let index = machine.registers()[A3].to_u64() as usize;
+                let source = source_from_register(machine.registers()[A4].to_u64());
+                let data = match source {
+                    Some(source) if code == SYS_LOAD_CELL => self.tx.cell(index, source),
+                    Some(source) => self.tx.cell_data(index, source),
+                    None => {
+                        machine.set_register(A0, Mac::REG::from_u64(CKB_INDEX_OUT_OF_BOUND));
+                        return Ok(true);
+                    }
+                };
+                Self::serve(machine, data, addr, addr_len, offset)?;
+            }
+            SYS_LOAD_WITNESS => {
+                let addr = machine.registers()[A0].to_u64();
+                let addr_len = machine.registers()[A1].to_u64();
+                let offset = machine.registers()[A2].to_u64();
+                let index = machine.registers()[A3].to_u64() as usize;
+                let source = source_from_register(machine.registers()[A4].to_u64());
+                match source {
+                    Some(Source::Input) => {
+                        let data = self.tx.witnesses.get(index).map(Vec::as_slice);
+                        Self::serve(machine, data, addr, addr_len, offset)?;
+                    }
+                    // `TxContext` models witnesses as a single flat array, not split by side,
+                    // so there is no real data to serve for these sources — see the module
+                    // doc's "Limitations" section.
+                    Some(Source::Output) | Some(Source::CellDep) | None => {
+                        machine.set_register(A0, Mac::REG::from_u64(CKB_INDEX_OUT_OF_BOUND));
+                    }
+                }
+            }
+            SYS_CKB_VM_ECDSA_RECOVER => {
+                let hash_addr = machine.registers()[A0].to_u64();
+                let sig_addr = machine.registers()[A1].to_u64();
+                let recovery_id = machine.registers()[A2].to_u64() as u8;
+                let out_addr = machine.registers()[A3].to_u64();
+
+                let mut message_hash = [0u8; 32];
+                message_hash.copy_from_slice(&machine.memory_mut().load_bytes(hash_addr, 32)?);
+                let mut signature = [0u8; 64];
+                signature.copy_from_slice(&machine.memory_mut().load_bytes(sig_addr, 64)?);
+
+                Self::ecdsa_recover(machine, &message_hash, &signature, recovery_id, out_addr)?;
+            }
+            SYS_DEBUG => {
+                // Scripts pass a debug string; there's no host-side log sink to forward it to
+                // during proving, so just acknowledge the call without reading the message.
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ckb_vm::{registers::A0, DefaultCoreMachine, SparseMemory};
+
+    fn new_machine() -> DefaultCoreMachine<u64, SparseMemory<u64>> {
+        DefaultCoreMachine::new(ckb_vm::ISA_IMC, ckb_vm::machine::VERSION2, u64::MAX)
+    }
+
+    #[test]
+    fn store_writes_back_the_full_remaining_length_not_the_copied_length() {
+        let mut machine = new_machine();
+        let len_addr = 0x1000u64;
+        let out_addr = 0x2000u64;
+        machine.memory_mut().store64(&len_addr, &4u64).unwrap();
+
+        CkbSyscalls::store(&mut machine, b"hello world", out_addr, len_addr, 0).unwrap();
+
+        let full_len = machine.memory_mut().load64(&len_addr).unwrap();
+        assert_eq!(full_len, 11);
+        let copied = machine.memory_mut().load_bytes(out_addr, 4).unwrap();
+        assert_eq!(&copied[..], b"hell");
+    }
+
+    #[test]
+    fn store_respects_the_offset() {
+        let mut machine = new_machine();
+        let len_addr = 0x1000u64;
+        let out_addr = 0x2000u64;
+        machine.memory_mut().store64(&len_addr, &100u64).unwrap();
+
+        CkbSyscalls::store(&mut machine, b"hello world", out_addr, len_addr, 6).unwrap();
+
+        let copied = machine.memory_mut().load_bytes(out_addr, 5).unwrap();
+        assert_eq!(&copied[..], b"world");
+    }
+
+    #[test]
+    fn store_writes_nothing_when_offset_is_past_the_end() {
+        let mut machine = new_machine();
+        let len_addr = 0x1000u64;
+        let out_addr = 0x2000u64;
+        machine.memory_mut().store64(&len_addr, &100u64).unwrap();
+
+        CkbSyscalls::store(&mut machine, b"hello", out_addr, len_addr, 5).unwrap();
+
+        let full_len = machine.memory_mut().load64(&len_addr).unwrap();
+        assert_eq!(full_len, 0);
+    }
+
+    #[test]
+    fn serve_reports_item_missing_for_absent_data() {
+        let mut machine = new_machine();
+        machine.memory_mut().store64(&0x1000u64, &32u64).unwrap();
+
+        CkbSyscalls::serve(&mut machine, None, 0x2000, 0x1000, 0).unwrap();
+
+        assert_eq!(machine.registers()[A0].to_u64(), CKB_ITEM_MISSING);
+    }
+
+    #[test]
+    fn source_from_register_collapses_group_variants() {
+        assert_eq!(source_from_register(SOURCE_INPUT), Some(Source::Input));
+        assert_eq!(
+            source_from_register(SOURCE_GROUP_INPUT),
+            Some(Source::Input)
+        );
+        assert_eq!(source_from_register(SOURCE_OUTPUT), Some(Source::Output));
+        assert_eq!(
+            source_from_register(SOURCE_GROUP_OUTPUT),
+            Some(Source::Output)
+        );
+        assert_eq!(
+            source_from_register(SOURCE_CELL_DEP),
+            Some(Source::CellDep)
+        );
+        assert_eq!(source_from_register(0xdead), None);
+    }
+
+    #[test]
+    fn serve_reports_success_and_copies_data_when_present() {
+        let mut machine = new_machine();
+        let len_addr = 0x1000u64;
+        let out_addr = 0x2000u64;
+        machine.memory_mut().store64(&len_addr, &5u64).unwrap();
+
+        CkbSyscalls::serve(&mut machine, Some(b"hello"), out_addr, len_addr, 0).unwrap();
+
+        assert_eq!(machine.registers()[A0].to_u64(), CKB_SUCCESS);
+        let copied = machine.memory_mut().load_bytes(out_addr, 5).unwrap();
+        assert_eq!(&copied[..], b"hello");
+    }
+
+    #[test]
+    fn ecdsa_recover_recovers_the_signing_pubkey() {
+        use k256::ecdsa::SigningKey;
+
+        let signing_key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let message_hash = [42u8; 32];
+        let (signature, recovery_id) = signing_key
+            .sign_prehash_recoverable(&message_hash)
+            .unwrap();
+        let signature_bytes: [u8; 64] = signature.to_bytes().into();
+
+        let mut machine = new_machine();
+        let out_addr = 0x3000u64;
+        CkbSyscalls::ecdsa_recover(
+            &mut machine,
+            &message_hash,
+            &signature_bytes,
+            recovery_id.to_byte(),
+            out_addr,
+        )
+        .unwrap();
+
+        assert_eq!(machine.registers()[A0].to_u64(), CKB_SUCCESS);
+        let expected_point = signing_key.verifying_key().to_encoded_point(false);
+        let recovered = machine.memory_mut().load_bytes(out_addr, 64).unwrap();
+        assert_eq!(&recovered[..], &expected_point.as_bytes()[1..]);
+    }
+
+    #[test]
+    fn ecdsa_recover_reports_invalid_data_for_a_garbage_signature() {
+        let mut machine = new_machine();
+        let message_hash = [0u8; 32];
+        let signature = [0u8; 64]; // all-zero r/s is never a valid signature
+
+        CkbSyscalls::ecdsa_recover(&mut machine, &message_hash, &signature, 0, 0x3000).unwrap();
+
+        assert_eq!(machine.registers()[A0].to_u64(), CKB_INVALID_DATA);
+    }
+}
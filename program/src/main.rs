@@ -1,5 +1,14 @@
-//! A simple program that takes a number `n` as input, and writes the `n-1`th and `n`th fibonacci
-//! number as an output.
+//! Runs an arbitrary CKB-VM program supplied by the host through `SP1Stdin`, so a single
+//! compiled ELF can prove the execution of any CKB script rather than one baked in at build
+//! time. The script runs against a real transaction context (cells, witnesses, its own args)
+//! served through [`ckb_syscalls::CkbSyscalls`], so scripts that load tx data through the
+//! syscalls it implements read that context; see its module doc for the CKB syscalls it does
+//! not handle.
+//!
+//! Public values layout (in commit order): CKB-VM program SHA256, args digest, tx hash, exit
+//! code, CKB-VM cycle count. Binding the program, args and tx hashes into the public values
+//! lets a verifier check that a proof covers the specific script/tx pair it expects, not just
+//! "some CKB-VM program ran and exited with code 0".
 
 // These two lines are necessary for the program to properly compile.
 //
@@ -8,24 +17,18 @@
 #![no_main]
 sp1_zkvm::entrypoint!(main);
 
-use ckb_vm::cost_model::estimate_cycles;
-use ckb_vm::{Bytes, DefaultMachineRunner, SupportMachine, Syscalls};
-
-const CODE: &[u8] = include_bytes!("secp256k1_ecdsa_ckbvm");
-
-pub struct DebugSyscall {}
+mod args_digest;
+mod ckb_syscalls;
 
-impl<Mac: SupportMachine> Syscalls<Mac> for DebugSyscall {
-    fn initialize(&mut self, _machine: &mut Mac) -> Result<(), ckb_vm::error::Error> {
-        Ok(())
-    }
-
-    fn ecall(&mut self, _machine: &mut Mac) -> Result<bool, ckb_vm::error::Error> {
-        Ok(true)
-    }
-}
+use args_digest::args_digest;
+use ckb_syscalls::{CkbSyscalls, TxContext};
+use ckb_vm::cost_model::estimate_cycles;
+use ckb_vm::{Bytes, DefaultMachineRunner, SupportMachine};
+use sha2::{Digest, Sha256};
 
-fn main_interpreter64(code: Bytes, args: Vec<Bytes>) {
+/// Runs `code` with `args` against `tx` to completion and returns the CKB-VM exit code and
+/// cycle count.
+fn main_interpreter64(code: Bytes, args: Vec<Bytes>, tx: TxContext) -> (i8, u64) {
     let core_machine = ckb_vm::DefaultCoreMachine::<u64, ckb_vm::SparseMemory<u64>>::new(
         ckb_vm::ISA_IMC | ckb_vm::ISA_B | ckb_vm::ISA_A | ckb_vm::ISA_MOP,
         ckb_vm::machine::VERSION2,
@@ -33,13 +36,31 @@ fn main_interpreter64(code: Bytes, args: Vec<Bytes>) {
     );
     let machine_builder = ckb_vm::RustDefaultMachineBuilder::new(core_machine)
         .instruction_cycle_func(Box::new(estimate_cycles));
-    let mut machine = machine_builder.syscall(Box::new(DebugSyscall {})).build();
+    let mut machine = machine_builder
+        .syscall(Box::new(CkbSyscalls { tx }))
+        .build();
     machine
         .load_program(&code, args.into_iter().map(Ok))
         .expect("load program");
-    machine.run().expect("run program");
+    let exit_code = machine.run().expect("run program");
+    (exit_code, machine.cycles())
 }
 
 fn main() {
-    main_interpreter64(CODE.into(), vec![])
+    let code: Vec<u8> = sp1_zkvm::io::read();
+    let args: Vec<Vec<u8>> = sp1_zkvm::io::read();
+    let tx: TxContext = sp1_zkvm::io::read();
+
+    let program_hash: [u8; 32] = Sha256::digest(&code).into();
+    let args: Vec<Bytes> = args.into_iter().map(Bytes::from).collect();
+    let args_hash = args_digest(&args);
+    let tx_hash = tx.tx_hash;
+
+    let (exit_code, cycles) = main_interpreter64(code.into(), args, tx);
+
+    sp1_zkvm::io::commit(&program_hash);
+    sp1_zkvm::io::commit(&args_hash);
+    sp1_zkvm::io::commit(&tx_hash);
+    sp1_zkvm::io::commit(&exit_code);
+    sp1_zkvm::io::commit(&cycles);
 }
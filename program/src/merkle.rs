@@ -0,0 +1,110 @@
+//! Pure Merkle-folding logic for the `--batch` aggregator, kept in its own module (rather than
+//! inline in `bin/aggregator.rs`) so it can be unit tested without pulling in that binary's
+//! `#![no_main]`/`sp1_zkvm::entrypoint!` zkVM harness.
+
+use sha2::{Digest, Sha256};
+
+/// Byte layout of one job's public values, as committed by the CKB-VM interpreter program:
+/// program hash (32) + args digest (32) + tx hash (32) + exit code (1) + cycle count (8).
+pub const JOB_PUBLIC_VALUES_LEN: usize = 32 + 32 + 32 + 1 + 8;
+
+/// Hashes the (program hash, args digest, tx hash, exit code, cycle count) tuple out of a
+/// job's raw public values. Binding the tx hash into the leaf (not just the program/args) is
+/// what lets a verifier confirm the aggregate proof actually covers the specific transactions
+/// it expects, rather than the same scripts run against arbitrary, unchecked cells.
+pub fn leaf_hash(public_values: &[u8]) -> [u8; 32] {
+    assert_eq!(
+        public_values.len(),
+        JOB_PUBLIC_VALUES_LEN,
+        "unexpected job public values length"
+    );
+    let mut hasher = Sha256::new();
+    hasher.update(&public_values[0..32]); // program hash
+    hasher.update(&public_values[32..64]); // args digest
+    hasher.update(&public_values[64..96]); // tx hash
+    hasher.update(&public_values[96..97]); // exit code
+    hasher.update(&public_values[97..105]); // cycle count
+    hasher.finalize().into()
+}
+
+/// Folds an ordered list of leaves into a single root by repeatedly hashing pairs,
+/// duplicating the last leaf when a level has an odd count.
+pub fn merkle_root(mut level: Vec<[u8; 32]>) -> [u8; 32] {
+    assert!(!level.is_empty(), "batch must contain at least one job");
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(*level.last().unwrap());
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut hasher = Sha256::new();
+                hasher.update(pair[0]);
+                hasher.update(pair[1]);
+                hasher.finalize().into()
+            })
+            .collect();
+    }
+    level[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public_values(program_hash: u8, args_hash: u8, tx_hash: u8, exit_code: i8, cycles: u64) -> Vec<u8> {
+        let mut pv = vec![0u8; JOB_PUBLIC_VALUES_LEN];
+        pv[0] = program_hash;
+        pv[32] = args_hash;
+        pv[64] = tx_hash;
+        pv[96] = exit_code as u8;
+        pv[97..105].copy_from_slice(&cycles.to_le_bytes());
+        pv
+    }
+
+    #[test]
+    fn leaf_hash_is_sensitive_to_tx_hash() {
+        let a = leaf_hash(&public_values(1, 2, 9, 0, 42));
+        let b = leaf_hash(&public_values(1, 2, 200, 0, 42));
+        assert_ne!(
+            a, b,
+            "the batch root must bind the transaction a job ran against, not just its program/args"
+        );
+    }
+
+    #[test]
+    fn leaf_hash_is_sensitive_to_program_args_exit_code_and_cycles() {
+        let base = leaf_hash(&public_values(1, 2, 0, 0, 42));
+        assert_ne!(base, leaf_hash(&public_values(2, 2, 0, 0, 42)));
+        assert_ne!(base, leaf_hash(&public_values(1, 3, 0, 0, 42)));
+        assert_ne!(base, leaf_hash(&public_values(1, 2, 0, 1, 42)));
+        assert_ne!(base, leaf_hash(&public_values(1, 2, 0, 0, 43)));
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected job public values length")]
+    fn leaf_hash_rejects_wrong_length() {
+        leaf_hash(&[0u8; 10]);
+    }
+
+    #[test]
+    fn merkle_root_of_single_leaf_is_itself() {
+        let leaf = [7u8; 32];
+        assert_eq!(merkle_root(vec![leaf]), leaf);
+    }
+
+    #[test]
+    fn merkle_root_duplicates_last_leaf_when_odd() {
+        let leaves = vec![[1u8; 32], [2u8; 32], [3u8; 32]];
+        let mut padded = leaves.clone();
+        padded.push([3u8; 32]);
+        assert_eq!(merkle_root(leaves), merkle_root(padded));
+    }
+
+    #[test]
+    fn merkle_root_is_order_sensitive() {
+        let a = merkle_root(vec![[1u8; 32], [2u8; 32]]);
+        let b = merkle_root(vec![[2u8; 32], [1u8; 32]]);
+        assert_ne!(a, b);
+    }
+}
@@ -10,30 +10,107 @@
 //!
 //! # Generate proof (requires significant resources)
 //! RUST_LOG=info cargo run --release -- --prove --mode vm
+//!
+//! # Prove a custom CKB script instead of the bundled secp256k1 fixture
+//! cargo run --release -- --execute --mode vm --ckb-program path/to/script.bin --ckb-arg 0011 --ckb-arg deadbeef
+//!
+//! # Prove a batch of CKB script executions as a single aggregated proof
+//! RUST_LOG=info cargo run --release -- --batch jobs.json
+//!
+//! # Compare interpreted vs. accelerated ECDSA recover cycle counts
+//! cargo run --release -- --minimal-execute --mode vm --ckb-program-accel contracts/ecdsa-recover-accel/target/riscv64imac-unknown-none-elf/release/ecdsa-recover-accel
+//!
+//! # CI performance gate: write a JSON report and fail on regression
+//! cargo run --release -- --minimal-execute --mode vm --report report.json --max-sp1-cycles 50000000
 //! ```
 
 use clap::{Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
 use sha2::Digest;
 use sp1_core_executor::{GasEstimatingVM, MinimalExecutor, Program, SP1CoreOpts};
 use sp1_hypercube::air::PROOF_NONCE_NUM_WORDS;
 use sp1_sdk::{
     include_elf, Elf, ProveRequest, Prover, ProverClient, ProvingKey, SP1PublicValues, SP1Stdin,
 };
+use std::path::PathBuf;
 use std::sync::Arc;
 
-/// The ELF for CKB-VM interpreter (runs k256_ecdsa inside CKB-VM)
+/// Mirrors `ckb_vm_interpreter_program::ckb_syscalls::TxContext` field-for-field: the guest
+/// and host crates don't share a library target, so this must stay in the same field order as
+/// the guest's struct for the `bincode`-serialized `SP1Stdin` payload to decode correctly.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TxContext {
+    tx_hash: [u8; 32],
+    input_cells: Vec<Vec<u8>>,
+    output_cells: Vec<Vec<u8>>,
+    input_cell_data: Vec<Vec<u8>>,
+    output_cell_data: Vec<Vec<u8>>,
+    #[serde(default)]
+    cell_deps: Vec<Vec<u8>>,
+    #[serde(default)]
+    cell_dep_data: Vec<Vec<u8>>,
+    witnesses: Vec<Vec<u8>>,
+    script: Vec<u8>,
+}
+
+/// The ELF for CKB-VM interpreter (runs an arbitrary CKB script inside CKB-VM, including k256
+/// ECDSA, p256 or recoverable-ECDSA verification scripts supplied via `--ckb-program`). Since
+/// the interpreter loads its CKB-VM bytecode from `SP1Stdin` rather than baking in a curve, the
+/// `Vm*` modes below all reuse this single ELF instead of each needing their own.
 pub const CKB_VM_INTERPRETER_ELF: Elf = include_elf!("ckb-vm-interpreter-program");
 
-/// The ELF for native k256_ecdsa (runs directly on SP1)
+/// The ELF for native k256 ECDSA verify (runs directly on SP1)
 pub const NATIVE_K256_ECDSA_ELF: &[u8] = include_bytes!("../../binaries/k256_ecdsa_sp1");
 
-#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+/// The ELF for native p256 (secp256r1) verify (runs directly on SP1)
+pub const NATIVE_P256_ELF: &[u8] = include_bytes!("../../binaries/p256_verify_sp1");
+
+/// The ELF for native recoverable k256 ECDSA (pubkey recovery, runs directly on SP1)
+pub const NATIVE_K256_RECOVER_ELF: &[u8] = include_bytes!("../../binaries/k256_ecdsa_recover_sp1");
+
+/// The CKB script run inside CKB-VM when `--ckb-program` is not given.
+const DEFAULT_CKB_PROGRAM: &[u8] = include_bytes!("../../../program/src/secp256k1_ecdsa_ckbvm");
+
+/// The ELF that aggregates a `--batch` run's per-job proofs into a single recursive proof.
+pub const CKB_VM_AGGREGATOR_ELF: Elf = include_elf!("ckb-vm-interpreter-aggregator-program");
+
+/// One CKB script execution in a `--batch` job file.
+#[derive(Debug, Clone, Deserialize)]
+struct BatchJob {
+    ckb_program: PathBuf,
+    #[serde(default)]
+    ckb_args: Vec<String>,
+    #[serde(default)]
+    ckb_tx: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
 enum Mode {
-    /// Run k256_ecdsa natively on SP1
+    /// Run k256 ECDSA verify natively on SP1
     Native,
-    /// Run k256_ecdsa inside CKB-VM interpreter on SP1
+    /// Run k256 ECDSA verify inside CKB-VM interpreter on SP1
     #[default]
     Vm,
+    /// Run p256 (secp256r1) verify natively on SP1
+    NativeP256,
+    /// Run a p256 (secp256r1) verify CKB script inside the CKB-VM interpreter on SP1. Point
+    /// `--ckb-program` at a p256 script; the interpreter ELF is the same one `Vm` uses.
+    VmP256,
+    /// Run recoverable k256 ECDSA (pubkey recovery from signature + recid) natively on SP1
+    NativeRecover,
+    /// Run a recoverable-ECDSA (pubkey recovery) CKB script inside the CKB-VM interpreter on
+    /// SP1. Point `--ckb-program` at such a script; the interpreter ELF is the same one `Vm`
+    /// uses.
+    VmRecover,
+}
+
+impl Mode {
+    /// Whether this mode runs its signature scheme inside the CKB-VM interpreter (as opposed
+    /// to natively on SP1). Vm modes commit the CKB-VM program hash, args digest and tx hash
+    /// before the exit code, and the CKB-VM cycle count after it.
+    fn is_vm(self) -> bool {
+        matches!(self, Mode::Vm | Mode::VmP256 | Mode::VmRecover)
+    }
 }
 
 /// The arguments for the command.
@@ -51,6 +128,244 @@ struct Args {
 
     #[arg(long, value_enum, default_value_t = Mode::Vm)]
     mode: Mode,
+
+    /// Path to the CKB-VM bytecode to run (vm modes only). Defaults to the bundled
+    /// secp256k1 fixture.
+    #[arg(long)]
+    ckb_program: Option<PathBuf>,
+
+    /// Hex-encoded CKB script argument. May be passed multiple times to build an argv.
+    #[arg(long = "ckb-arg")]
+    ckb_args: Vec<String>,
+
+    /// Path to a JSON-encoded transaction context (cells, witnesses, script) the CKB script
+    /// can load through `ckb_load_*` syscalls. Defaults to an empty context: the cell/cell-data
+    /// and (`Source::Input`) witness loaders then return `ITEM_MISSING`, but `ckb_load_tx_hash`
+    /// and `ckb_load_script` always succeed, reading an all-zero hash and an empty script.
+    #[arg(long)]
+    ckb_tx: Option<PathBuf>,
+
+    /// Path to a variant of `--ckb-program` that calls the accelerated ECDSA-recover syscall
+    /// instead of verifying secp256k1 signatures in interpreted CKB-VM — e.g. the compiled ELF
+    /// of `contracts/ecdsa-recover-accel`, which exercises
+    /// `ckb_syscalls::SYS_CKB_VM_ECDSA_RECOVER` end-to-end. With `--minimal-execute --mode vm`,
+    /// running both prints the CKB-VM/SP1 cycle delta between the two.
+    #[arg(long)]
+    ckb_program_accel: Option<PathBuf>,
+
+    /// Path to a JSON file listing CKB script executions to prove as a single aggregated
+    /// proof, mutually exclusive with `--execute`/`--prove`/`--minimal-execute`.
+    #[arg(long)]
+    batch: Option<PathBuf>,
+
+    /// Write a machine-readable `--minimal-execute` report (cycles, gas, ELF hash) to this
+    /// path as JSON, for diffing performance across commits in CI.
+    #[arg(long)]
+    report: Option<PathBuf>,
+
+    /// With `--minimal-execute`, fail (non-zero exit) if the SP1 cycle count exceeds this
+    /// budget. Use as a CI performance gate against regressions.
+    #[arg(long)]
+    max_sp1_cycles: Option<u64>,
+}
+
+/// Loads a transaction context from a `--ckb-tx`-style JSON path, or the empty default when
+/// none is given.
+fn load_tx_context(path: Option<&PathBuf>) -> TxContext {
+    match path {
+        Some(path) => {
+            let raw = std::fs::read(path).expect("failed to read tx context file");
+            serde_json::from_slice(&raw).expect("tx context file must be valid JSON")
+        }
+        None => TxContext::default(),
+    }
+}
+
+/// Builds the `SP1Stdin` the CKB-VM interpreter guest reads its program, args and transaction
+/// context from.
+fn build_ckb_vm_stdin(code: Vec<u8>, script_args: Vec<Vec<u8>>, tx: TxContext) -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&code);
+    stdin.write(&script_args);
+    stdin.write(&tx);
+    stdin
+}
+
+/// Builds the CKB-VM interpreter guest's `SP1Stdin` from CLI flags. `program_override`, when
+/// given, replaces `--ckb-program` (used to run the accelerated variant passed via
+/// `--ckb-program-accel`).
+fn ckb_vm_stdin(args: &Args, program_override: Option<&PathBuf>) -> SP1Stdin {
+    let code = match program_override.or(args.ckb_program.as_ref()) {
+        Some(path) => std::fs::read(path).expect("failed to read CKB program"),
+        None => DEFAULT_CKB_PROGRAM.to_vec(),
+    };
+    let script_args: Vec<Vec<u8>> = args
+        .ckb_args
+        .iter()
+        .map(|arg| hex::decode(arg).expect("--ckb-arg must be hex-encoded"))
+        .collect();
+    let tx = load_tx_context(args.ckb_tx.as_ref());
+
+    build_ckb_vm_stdin(code, script_args, tx)
+}
+
+/// Result of running `--minimal-execute` once, used both for console output and to build the
+/// `--report` JSON and compare before/after cycle deltas when benchmarking the accelerated
+/// CKB-VM ECDSA path.
+struct MinimalExecuteResult {
+    exit_code: i8,
+    ckb_vm_cycles: Option<u64>,
+    sp1_instructions: u64,
+    /// Gas reported by `GasEstimatingVM::execute` for each `execute_chunk` call, in order.
+    chunk_gas: Vec<u64>,
+}
+
+/// Runs `elf_bytes` with the raw, already-`bincode`-serialized `SP1Stdin` bytes through
+/// `MinimalExecutor`/`GasEstimatingVM`, returning the exit code, CKB-VM cycle count (vm modes
+/// only), SP1 instruction count and per-chunk gas.
+fn run_minimal_execute(elf_bytes: &[u8], input: &[u8], is_vm_mode: bool) -> MinimalExecuteResult {
+    let program = Arc::new(Program::from(elf_bytes).unwrap());
+    let mut executor = MinimalExecutor::new(program.clone(), false, Some(1000));
+    executor.with_input(input);
+
+    let proof_nonce: [u32; PROOF_NONCE_NUM_WORDS] = [0; PROOF_NONCE_NUM_WORDS];
+    let opts = SP1CoreOpts::default();
+    let mut chunk_gas = Vec::new();
+
+    while !executor.is_done() {
+        let trace_chunk = executor.execute_chunk().unwrap();
+        let mut gas_vm =
+            GasEstimatingVM::new(&trace_chunk, program.clone(), proof_nonce, opts.clone());
+        chunk_gas.push(gas_vm.execute().unwrap());
+    }
+
+    let mut public_values = SP1PublicValues::from(executor.public_values_stream().as_slice());
+    if is_vm_mode {
+        let _program_hash: [u8; 32] = public_values.read();
+        let _args_hash: [u8; 32] = public_values.read();
+        let _tx_hash: [u8; 32] = public_values.read();
+    }
+    let exit_code = public_values.read::<i8>();
+    let ckb_vm_cycles = is_vm_mode.then(|| public_values.read::<u64>());
+
+    if exit_code != 0 {
+        panic!("exit code is not 0");
+    }
+    if executor.exit_code() != 0 {
+        panic!("sp1 exit code is not 0");
+    }
+
+    MinimalExecuteResult {
+        exit_code,
+        ckb_vm_cycles,
+        sp1_instructions: executor.global_clk(),
+        chunk_gas,
+    }
+}
+
+/// Schema version of the `--report` JSON. Bump when the field set or meaning changes so
+/// downstream tooling can detect incompatible reports.
+const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Machine-readable `--minimal-execute` report, written to `--report <file>` so CI can diff
+/// performance across commits.
+#[derive(Debug, Serialize)]
+struct MinimalExecuteReport {
+    schema_version: u32,
+    mode: String,
+    elf_sha256: String,
+    exit_code: i8,
+    ckb_vm_cycles: Option<u64>,
+    sp1_instructions: u64,
+    sp1_cycles: u64,
+    chunk_gas: Vec<u64>,
+}
+
+/// Whether `sp1_cycles` exceeds a `--max-sp1-cycles` budget; always `false` when no budget was
+/// given, matching `--max-sp1-cycles` being purely opt-in.
+fn exceeds_cycle_budget(sp1_cycles: u64, max_sp1_cycles: Option<u64>) -> bool {
+    max_sp1_cycles.is_some_and(|max| sp1_cycles > max)
+}
+
+/// Proves every job in `batch_path` against the CKB-VM interpreter ELF, then aggregates the
+/// per-job compressed proofs into a single recursive proof whose public values commit a
+/// Merkle root over the jobs' (program hash, args digest, tx hash, exit code, cycle count)
+/// tuples.
+async fn run_batch(batch_path: &std::path::Path) {
+    let jobs: Vec<BatchJob> =
+        serde_json::from_slice(&std::fs::read(batch_path).expect("failed to read --batch file"))
+            .expect("--batch file must be a JSON array of jobs");
+    if jobs.is_empty() {
+        eprintln!("Error: --batch file lists no jobs");
+        std::process::exit(1);
+    }
+
+    let client = ProverClient::from_env().await;
+    let pk = client
+        .setup(CKB_VM_INTERPRETER_ELF)
+        .await
+        .expect("setup failed");
+
+    let mut job_proofs = Vec::with_capacity(jobs.len());
+    for (i, job) in jobs.iter().enumerate() {
+        let code = std::fs::read(&job.ckb_program).expect("failed to read job ckb_program");
+        let script_args: Vec<Vec<u8>> = job
+            .ckb_args
+            .iter()
+            .map(|arg| hex::decode(arg).expect("job ckb_args must be hex-encoded"))
+            .collect();
+        let tx = load_tx_context(job.ckb_tx.as_ref());
+        let stdin = build_ckb_vm_stdin(code, script_args, tx);
+
+        println!("Proving batch job {}/{}...", i + 1, jobs.len());
+        let proof = client
+            .prove(&pk, stdin)
+            .compressed()
+            .await
+            .expect("failed to prove batch job");
+        job_proofs.push(proof);
+    }
+
+    let agg_pk = client
+        .setup(CKB_VM_AGGREGATOR_ELF)
+        .await
+        .expect("aggregator setup failed");
+
+    // `write_proof` only registers each STARK proof with the recursion prover; it does not
+    // populate the plain input stream the aggregator guest reads with `sp1_zkvm::io::read()`.
+    // The guest also needs the vkey and raw public values as ordinary inputs so it can compute
+    // the public-values digest and call `verify_sp1_proof` itself, so write both alongside the
+    // proofs below, in the same order the guest reads them (job_count, then the `vkeys` vector,
+    // then the `public_values` vector).
+    let vkeys: Vec<[u32; 8]> = job_proofs.iter().map(|_| pk.verifying_key().hash_u32()).collect();
+    let public_values: Vec<Vec<u8>> = job_proofs
+        .iter()
+        .map(|proof| proof.public_values.to_vec())
+        .collect();
+
+    let mut agg_stdin = SP1Stdin::new();
+    agg_stdin.write(&(job_proofs.len() as u64));
+    agg_stdin.write(&vkeys);
+    agg_stdin.write(&public_values);
+    for proof in job_proofs {
+        agg_stdin.write_proof(proof, pk.verifying_key().clone());
+    }
+
+    let agg_proof = client
+        .prove(&agg_pk, agg_stdin)
+        .compressed()
+        .await
+        .expect("failed to prove aggregate");
+
+    client
+        .verify(&agg_proof, agg_pk.verifying_key(), None)
+        .expect("failed to verify aggregate proof");
+
+    let mut public_values = agg_proof.public_values.clone();
+    let merkle_root: [u8; 32] = public_values.read();
+    let job_count: u64 = public_values.read();
+    println!("Aggregated {} CKB script execution(s)", job_count);
+    println!("Merkle root: {}", hex::encode(merkle_root));
 }
 
 #[tokio::main]
@@ -60,82 +375,154 @@ async fn main() {
 
     let args = Args::parse();
 
-    let options_count = args.execute as u8 + args.prove as u8 + args.minimal_execute as u8;
+    let options_count = args.execute as u8
+        + args.prove as u8
+        + args.minimal_execute as u8
+        + args.batch.is_some() as u8;
     if options_count != 1 {
         eprintln!(
-            "Error: You must specify exactly one of --execute, --prove, or --minimal-execute"
+            "Error: You must specify exactly one of --execute, --prove, --minimal-execute, or --batch"
         );
         std::process::exit(1);
     }
 
+    if let Some(batch_path) = &args.batch {
+        run_batch(batch_path).await;
+        return;
+    }
+
     let (elf_bytes, mode_desc): (&[u8], &str) = match args.mode {
         Mode::Native => (
             NATIVE_K256_ECDSA_ELF,
-            "native (k256_ecdsa runs directly on SP1)",
+            "native (k256 ECDSA verify runs directly on SP1)",
         ),
         Mode::Vm => (
             &CKB_VM_INTERPRETER_ELF,
-            "vm (k256_ecdsa runs inside CKB-VM on SP1)",
+            "vm (k256 ECDSA verify runs inside CKB-VM on SP1)",
+        ),
+        Mode::NativeP256 => (
+            NATIVE_P256_ELF,
+            "native-p256 (p256 verify runs directly on SP1)",
+        ),
+        Mode::VmP256 => (
+            &CKB_VM_INTERPRETER_ELF,
+            "vm-p256 (p256 verify CKB script runs inside CKB-VM on SP1; pass it via --ckb-program)",
+        ),
+        Mode::NativeRecover => (
+            NATIVE_K256_RECOVER_ELF,
+            "native-recover (k256 pubkey recovery runs directly on SP1)",
+        ),
+        Mode::VmRecover => (
+            &CKB_VM_INTERPRETER_ELF,
+            "vm-recover (recoverable-ECDSA CKB script runs inside CKB-VM on SP1; pass it via --ckb-program)",
         ),
     };
 
     if args.minimal_execute {
-        let program = Arc::new(Program::from(elf_bytes).unwrap());
-        let mut executor = MinimalExecutor::new(program.clone(), false, Some(1000));
-
-        executor.with_input(&[]);
-
-        let proof_nonce: [u32; PROOF_NONCE_NUM_WORDS] = [0; PROOF_NONCE_NUM_WORDS];
-        let opts = SP1CoreOpts::default();
-
-        while !executor.is_done() {
-            let trace_chunk = executor.execute_chunk().unwrap();
-            let mut gas_vm =
-                GasEstimatingVM::new(&trace_chunk, program.clone(), proof_nonce, opts.clone());
-            let _ = gas_vm.execute().unwrap();
-        }
-
-        let mut public_values = SP1PublicValues::from(executor.public_values_stream().as_slice());
-        let exit_code = public_values.read::<i8>();
+        let is_vm_mode = args.mode.is_vm();
+        let input = if is_vm_mode {
+            let stdin = ckb_vm_stdin(&args, None);
+            bincode::serialize(&stdin).expect("serialize stdin")
+        } else {
+            vec![]
+        };
+        let result = run_minimal_execute(elf_bytes, &input, is_vm_mode);
 
         println!("Mode: {}", mode_desc);
-        println!("Exit code: {}", exit_code);
-        if matches!(args.mode, Mode::Vm) {
-            let ckb_vm_cycles = public_values.read::<u64>();
+        println!("Exit code: {}", result.exit_code);
+        if let Some(ckb_vm_cycles) = result.ckb_vm_cycles {
             println!("CKB-VM cycles: {}", ckb_vm_cycles);
         }
         println!(
             "SP1 instruction executed: {:.2}M",
-            executor.global_clk() as f64 / 1_000_000.0
+            result.sp1_instructions as f64 / 1_000_000.0
         );
         println!(
             "SP1 cycles: {:.2}M",
-            (executor.global_clk() * 8) as f64 / 1_000_000.0
+            (result.sp1_instructions * 8) as f64 / 1_000_000.0
         );
         let hash = sha2::Sha256::digest(elf_bytes);
-        println!("ELF SHA256: {}", hex::encode(hash));
+        let elf_sha256 = hex::encode(hash);
+        println!("ELF SHA256: {}", elf_sha256);
 
-        if exit_code != 0 {
-            panic!("exit code is not 0");
+        let sp1_cycles = result.sp1_instructions * 8;
+        if let Some(report_path) = &args.report {
+            let report = MinimalExecuteReport {
+                schema_version: REPORT_SCHEMA_VERSION,
+                mode: format!("{:?}", args.mode),
+                elf_sha256,
+                exit_code: result.exit_code,
+                ckb_vm_cycles: result.ckb_vm_cycles,
+                sp1_instructions: result.sp1_instructions,
+                sp1_cycles,
+                chunk_gas: result.chunk_gas.clone(),
+            };
+            std::fs::write(
+                report_path,
+                serde_json::to_string_pretty(&report).expect("serialize report"),
+            )
+            .expect("failed to write --report file");
         }
-        if executor.exit_code() != 0 {
-            panic!("sp1 exit code is not 0");
+
+        if exceeds_cycle_budget(sp1_cycles, args.max_sp1_cycles) {
+            eprintln!(
+                "Error: SP1 cycle count {} exceeds --max-sp1-cycles budget {}",
+                sp1_cycles,
+                args.max_sp1_cycles.unwrap()
+            );
+            std::process::exit(1);
+        }
+
+        if let Some(accel_path) = &args.ckb_program_accel {
+            if !is_vm_mode {
+                eprintln!("Warning: --ckb-program-accel only applies to vm modes, ignoring");
+            } else {
+                let accel_stdin = ckb_vm_stdin(&args, Some(accel_path));
+                let accel_input = bincode::serialize(&accel_stdin).expect("serialize stdin");
+                let accel_result = run_minimal_execute(&CKB_VM_INTERPRETER_ELF, &accel_input, true);
+
+                println!("--- accelerated ECDSA recover ({:?}) ---", accel_path);
+                println!(
+                    "CKB-VM cycles: {} (delta: {})",
+                    accel_result.ckb_vm_cycles.unwrap_or_default(),
+                    accel_result.ckb_vm_cycles.unwrap_or_default() as i64
+                        - result.ckb_vm_cycles.unwrap_or_default() as i64
+                );
+                println!(
+                    "SP1 instructions: {:.2}M (delta: {:.2}M)",
+                    accel_result.sp1_instructions as f64 / 1_000_000.0,
+                    (accel_result.sp1_instructions as i64 - result.sp1_instructions as i64) as f64
+                        / 1_000_000.0
+                );
+            }
         }
 
         return;
     }
 
     let client = ProverClient::from_env().await;
-    let stdin = SP1Stdin::new();
+    let stdin = if args.mode.is_vm() {
+        ckb_vm_stdin(&args, None)
+    } else {
+        SP1Stdin::new()
+    };
 
     if args.execute {
         let (mut public_values, report) =
             client.execute(Elf::Static(elf_bytes), stdin).await.unwrap();
-        let exit_code = public_values.read::<i8>();
 
         println!("Mode: {}", mode_desc);
+        if args.mode.is_vm() {
+            let program_hash: [u8; 32] = public_values.read();
+            let args_hash: [u8; 32] = public_values.read();
+            let tx_hash: [u8; 32] = public_values.read();
+            println!("CKB-VM program SHA256: {}", hex::encode(program_hash));
+            println!("CKB script args digest: {}", hex::encode(args_hash));
+            println!("Tx hash: {}", hex::encode(tx_hash));
+        }
+        let exit_code = public_values.read::<i8>();
         println!("Exit code: {}", exit_code);
-        if matches!(args.mode, Mode::Vm) {
+        if args.mode.is_vm() {
             let ckb_vm_cycles = public_values.read::<u64>();
             println!("CKB-VM cycles: {}", ckb_vm_cycles);
         }
@@ -173,3 +560,75 @@ async fn main() {
         println!("Successfully verified proof!");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mode_is_vm_matches_only_the_vm_variants() {
+        assert!(!Mode::Native.is_vm());
+        assert!(Mode::Vm.is_vm());
+        assert!(!Mode::NativeP256.is_vm());
+        assert!(Mode::VmP256.is_vm());
+        assert!(!Mode::NativeRecover.is_vm());
+        assert!(Mode::VmRecover.is_vm());
+    }
+
+    #[test]
+    fn exceeds_cycle_budget_is_false_when_no_budget_is_set() {
+        assert!(!exceeds_cycle_budget(u64::MAX, None));
+    }
+
+    #[test]
+    fn exceeds_cycle_budget_is_false_at_or_under_the_budget() {
+        assert!(!exceeds_cycle_budget(100, Some(100)));
+        assert!(!exceeds_cycle_budget(99, Some(100)));
+    }
+
+    #[test]
+    fn exceeds_cycle_budget_is_true_over_the_budget() {
+        assert!(exceeds_cycle_budget(101, Some(100)));
+    }
+
+    #[test]
+    fn minimal_execute_report_schema_matches_report_schema_version() {
+        let report = MinimalExecuteReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            mode: "Vm".to_string(),
+            elf_sha256: "deadbeef".to_string(),
+            exit_code: 0,
+            ckb_vm_cycles: Some(42),
+            sp1_instructions: 1_000,
+            sp1_cycles: 8_000,
+            chunk_gas: vec![1, 2, 3],
+        };
+
+        let json = serde_json::to_value(&report).expect("serialize report");
+        assert_eq!(json["schema_version"], REPORT_SCHEMA_VERSION);
+        assert_eq!(json["mode"], "Vm");
+        assert_eq!(json["elf_sha256"], "deadbeef");
+        assert_eq!(json["exit_code"], 0);
+        assert_eq!(json["ckb_vm_cycles"], 42);
+        assert_eq!(json["sp1_instructions"], 1_000);
+        assert_eq!(json["sp1_cycles"], 8_000);
+        assert_eq!(json["chunk_gas"], serde_json::json!([1, 2, 3]));
+    }
+
+    #[test]
+    fn minimal_execute_report_omits_ckb_vm_cycles_as_null_for_native_modes() {
+        let report = MinimalExecuteReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            mode: "Native".to_string(),
+            elf_sha256: "deadbeef".to_string(),
+            exit_code: 0,
+            ckb_vm_cycles: None,
+            sp1_instructions: 1_000,
+            sp1_cycles: 8_000,
+            chunk_gas: vec![],
+        };
+
+        let json = serde_json::to_value(&report).expect("serialize report");
+        assert!(json["ckb_vm_cycles"].is_null());
+    }
+}